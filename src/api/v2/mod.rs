@@ -236,6 +236,7 @@ mod tests {
 		},
 		data::Key,
 		data::{mem_db, Database},
+		network::rpc::FinalityStall,
 		types::{BlockRange, OptionBlockRange, RuntimeConfig, State},
 	};
 	use async_trait::async_trait;
@@ -254,6 +255,7 @@ mod tests {
 		collections::HashSet,
 		str::FromStr,
 		sync::{Arc, Mutex},
+		time::Duration,
 	};
 	use subxt::config::substrate::Digest;
 	use test_case::test_case;
@@ -342,6 +344,31 @@ mod tests {
 		assert_eq!(response.body(), &expected);
 	}
 
+	#[tokio::test]
+	async fn status_route_finality_stall() {
+		let state = Arc::new(Mutex::new(State::default()));
+		state.lock().unwrap().finality_stall = Some(FinalityStall::AuthoritySetMismatch {
+			set_id: 5,
+			pending_headers: 3,
+			pending_justifications: 2,
+			pending_for: Duration::from_secs(120),
+		});
+
+		let route = super::status_route(RuntimeConfig::default(), state);
+		let response = warp::test::request()
+			.method("GET")
+			.path("/v2/status")
+			.reply(&route)
+			.await;
+
+		let gen_hash = H256::default();
+		let expected = format!(
+			r#"{{"modes":["light"],"genesis_hash":"{:x?}","network":"{NETWORK}","blocks":{{"latest":0}},"finality_stall":{{"reason":"authority-set-mismatch","set_id":5,"pending_headers":3,"pending_justifications":2,"pending_for":120}}}}"#,
+			gen_hash
+		);
+		assert_eq!(response.body(), &expected);
+	}
+
 	#[test_case(1, 2)]
 	#[test_case(10, 11)]
 	#[test_case(10, 20)]
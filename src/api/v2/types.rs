@@ -25,7 +25,7 @@ use warp::{
 };
 
 use crate::{
-	network::rpc::Event as RpcEvent,
+	network::rpc::{self, Event as RpcEvent},
 	types::{
 		self, block_matrix_partition_format, BlockVerified, OptionBlockRange, RuntimeConfig, State,
 	},
@@ -97,6 +97,63 @@ pub struct Status {
 		with = "block_matrix_partition_format"
 	)]
 	pub partition: Option<Partition>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub finality_stall: Option<FinalityStall>,
+}
+
+/// Reason why finalized headers are not getting verified, with pending time in seconds
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "kebab-case")]
+pub enum FinalityStall {
+	NoJustifications {
+		pending_headers: usize,
+		pending_for: u64,
+	},
+	UnmatchedJustifications {
+		pending_headers: usize,
+		pending_justifications: usize,
+		pending_for: u64,
+	},
+	AuthoritySetMismatch {
+		set_id: u64,
+		pending_headers: usize,
+		pending_justifications: usize,
+		pending_for: u64,
+	},
+}
+
+impl From<&rpc::FinalityStall> for FinalityStall {
+	fn from(value: &rpc::FinalityStall) -> Self {
+		match *value {
+			rpc::FinalityStall::NoJustifications {
+				pending_headers,
+				pending_for,
+			} => FinalityStall::NoJustifications {
+				pending_headers,
+				pending_for: pending_for.as_secs(),
+			},
+			rpc::FinalityStall::UnmatchedJustifications {
+				pending_headers,
+				pending_justifications,
+				pending_for,
+			} => FinalityStall::UnmatchedJustifications {
+				pending_headers,
+				pending_justifications,
+				pending_for: pending_for.as_secs(),
+			},
+			rpc::FinalityStall::AuthoritySetMismatch {
+				set_id,
+				pending_headers,
+				pending_justifications,
+				pending_for,
+			} => FinalityStall::AuthoritySetMismatch {
+				set_id,
+				pending_headers,
+				pending_justifications,
+				pending_for: pending_for.as_secs(),
+			},
+		}
+	}
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
@@ -183,6 +240,7 @@ impl Status {
 			network: node.network(),
 			blocks,
 			partition: config.block_matrix_partition,
+			finality_stall: state.finality_stall.as_ref().map(From::from),
 		}
 	}
 }
@@ -1,12 +1,19 @@
 use avail_light::{
 	data::rocks_db::RocksDB,
 	network::rpc,
-	types::{ExponentialConfig, RetryConfig, State},
+	types::{ExponentialConfig, RetryConfig, RuntimeConfig, State, TransactionSource},
+};
+use avail_subxt::{
+	api::{self, runtime_types::bounded_collections::bounded_vec::BoundedVec},
+	primitives::AvailExtrinsicParams,
+	AvailConfig,
 };
 use clap::Parser;
 use color_eyre::{eyre::Context, Result};
 use kate_recovery::matrix::Position;
+use sp_core::{sr25519, Pair};
 use std::sync::{Arc, Mutex};
+use subxt::tx::PairSigner;
 
 #[derive(Parser)]
 struct CommandArgs {
@@ -31,8 +38,15 @@ async fn main() -> Result<()> {
 		retries: 4,
 	});
 
-	let (rpc_client, _, subscriptions) =
-		rpc::init(db, state, &[command_args.url], "DEV", retry_cfg).await?;
+	let (rpc_client, _, subscriptions) = rpc::init(
+		db,
+		state,
+		&[command_args.url],
+		"DEV",
+		retry_cfg,
+		(&RuntimeConfig::default()).into(),
+	)
+	.await?;
 	tokio::spawn(subscriptions.run());
 
 	let mut correct: bool = true;
@@ -81,6 +95,14 @@ async fn main() -> Result<()> {
 	let res = rpc_client.get_validator_set_by_hash(hash).await;
 	res_helper(&res, &mut correct);
 
+	print!("Testing get grandpa authorities by hash... ");
+	let res = rpc_client.get_grandpa_authorities_by_hash(hash).await;
+	res_helper(&res, &mut correct);
+
+	print!("Testing get grandpa set_id by hash... ");
+	let res = rpc_client.get_grandpa_set_id_by_hash(hash).await;
+	res_helper(&res, &mut correct);
+
 	print!("Testing get set_id at height 1... ");
 	let res = rpc_client.fetch_set_id_at(hash).await;
 	res_helper(&res, &mut correct);
@@ -104,6 +126,49 @@ async fn main() -> Result<()> {
 	let res = rpc_client.request_kate_rows(vec![0], hash).await;
 	res_helper(&res, &mut correct);
 
+	// signed by development account, so it can be validated against a development chain
+	let signer = <PairSigner<AvailConfig, sr25519::Pair>>::new(
+		sr25519::Pair::from_string("//Alice", None).wrap_err("Invalid development account")?,
+	);
+	let call = api::tx()
+		.data_availability()
+		.submit_data(BoundedVec(b"api compatibility test".to_vec()));
+	let tx_bytes = rpc_client
+		.current_client()
+		.await
+		.tx()
+		.create_signed(
+			&call,
+			&signer,
+			AvailExtrinsicParams::new_with_app_id(0.into()),
+		)
+		.await
+		.wrap_err("Cannot create signed transaction")?
+		.into_encoded();
+
+	print!("Testing validate transaction... ");
+	let res = rpc_client
+		.validate_transaction(hash, TransactionSource::External, &tx_bytes)
+		.await;
+	res_helper(&res, &mut correct);
+	if let Ok(v) = res {
+		println!("Reported transaction validity: {v:?}")
+	};
+
+	print!("Testing get account nonce... ");
+	let res = rpc_client
+		.get_account_nonce(hash, signer.account_id())
+		.await;
+	res_helper(&res, &mut correct);
+
+	print!("Testing query fee info... ");
+	let res = rpc_client.query_fee_info(hash, &tx_bytes).await;
+	res_helper(&res, &mut correct);
+
+	print!("Testing query fee details... ");
+	let res = rpc_client.query_fee_details(hash, &tx_bytes).await;
+	res_helper(&res, &mut correct);
+
 	println!("Done");
 	if !correct {
 		std::process::exit(1);
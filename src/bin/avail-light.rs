@@ -215,6 +215,7 @@ async fn run(shutdown: Controller<String>) -> Result<()> {
 		&cfg.full_node_ws,
 		&cfg.genesis_hash,
 		cfg.retry_config.clone(),
+		(&cfg).into(),
 	)
 	.await?;
 
@@ -367,6 +368,7 @@ async fn run(shutdown: Controller<String>) -> Result<()> {
 			shutdown.clone(),
 			state.clone(),
 			block_header.clone(),
+			cfg.finality_sync_checkpoint.clone(),
 		)));
 	} else {
 		let mut s = state
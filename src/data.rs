@@ -51,7 +51,7 @@ pub enum Key {
 	FinalitySyncCheckpoint,
 }
 
-#[derive(Serialize, Deserialize, Debug, Decode, Encode)]
+#[derive(Serialize, Deserialize, Clone, Debug, Decode, Encode)]
 pub struct FinalitySyncCheckpoint {
 	pub number: u32,
 	pub set_id: u64,
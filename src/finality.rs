@@ -1,6 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use codec::Encode;
+use avail_subxt::primitives::Header;
+use codec::{Decode, Encode};
 use sp_core::{
 	blake2_256,
 	ed25519::{self, Public},
@@ -8,18 +9,58 @@ use sp_core::{
 };
 use tracing::{info, warn};
 
-use crate::types::{GrandpaJustification, SignerMessage};
-use color_eyre::{eyre::eyre, Result};
+use crate::{
+	types::{
+		Commit, CommitMessage, Equivocation, EquivocationProof, GrandpaEquivocation,
+		GrandpaJustification, Prevote, SignedPrecommit, SignerMessage,
+	},
+	utils::{filter_scheduled_auth_set_changes, has_forced_auth_set_change},
+};
+use color_eyre::{
+	eyre::{eyre, WrapErr},
+	Result,
+};
 
 #[derive(Clone, Debug)]
 pub struct ValidatorSet {
 	pub set_id: u64,
 	pub validator_set: Vec<Public>,
+	/// Indices of validators disabled by `OnDisabled` consensus log, until the next set change.
+	/// Votes of disabled validators are still counted, but finalization which depends on them is reported.
+	pub disabled: HashSet<u64>,
+}
+
+impl ValidatorSet {
+	fn is_enabled(&self, public: &Public) -> bool {
+		self.validator_set
+			.iter()
+			.enumerate()
+			.any(|(index, e)| e.0.eq(&public.0) && !self.disabled.contains(&(index as u64)))
+	}
 }
 
 pub fn check_finality(
 	validator_set: &ValidatorSet,
 	justification: &GrandpaJustification,
+) -> Result<()> {
+	verify_justification(validator_set, justification, false)
+}
+
+/// Verifies justification received from untrusted source (gossip, warp sync proof).
+///
+/// Signatures are checked only against the set ID of the given validator set,
+/// and justification containing multiple precommits of the same signer is rejected.
+pub fn check_finality_strict(
+	validator_set: &ValidatorSet,
+	justification: &GrandpaJustification,
+) -> Result<()> {
+	verify_justification(validator_set, justification, true)
+}
+
+fn verify_justification(
+	validator_set: &ValidatorSet,
+	justification: &GrandpaJustification,
+	strict: bool,
 ) -> Result<()> {
 	let ancestry_map: HashMap<H256, H256> = justification
 		.votes_ancestries
@@ -50,12 +91,13 @@ pub fn check_finality(
 				signed_message,
 				&precommit.id,
 			);
-			if !is_ok {
+			if !is_ok && !strict {
 				warn!(
 					"Signature verification fails with default set_id {}, trying alternatives.",
 					validator_set.set_id
 				);
-				for set_id_m in (validator_set.set_id - 10)..(validator_set.set_id + 10) {
+				for set_id_m in validator_set.set_id.saturating_sub(10)..(validator_set.set_id + 10)
+				{
 					let s_m = Encode::encode(&(
 						&SignerMessage::PrecommitMessage(precommit.precommit.clone()),
 						&justification.round,
@@ -86,14 +128,38 @@ pub fn check_finality(
 			)
 				})
 		})
-		.collect::<Result<Vec<_>>>();
+		.collect::<Result<Vec<_>>>()?;
+
+	// each signer is counted only once
+	let mut signers = HashSet::new();
+	for id in signer_addresses {
+		if !signers.insert(id) && strict {
+			return Err(eyre!("Multiple precommits signed by {id:?}"));
+		}
+	}
 
 	// match all the Signer addresses to the Current Validator Set
-	let num_matched_addresses = signer_addresses?
+	let num_matched_addresses = signers
 		.iter()
 		.filter(|x| validator_set.validator_set.iter().any(|e| e.0.eq(&x.0)))
 		.count();
 
+	// votes from disabled validators are still counted, same as in the Substrate justification verification
+	let num_enabled_addresses = signers
+		.iter()
+		.filter(|x| validator_set.is_enabled(x))
+		.count();
+	let num_validators = validator_set.validator_set.len();
+	if is_signed_by_supermajority(num_matched_addresses, num_validators)
+		&& !is_signed_by_supermajority(num_enabled_addresses, num_validators)
+	{
+		warn!(
+			"Block {} is finalized only with votes of disabled validators ({} of {num_matched_addresses})",
+			justification.commit.target_number,
+			num_matched_addresses - num_enabled_addresses
+		);
+	}
+
 	info!(
 		"Number of matching signatures: {num_matched_addresses}/{} for block {}, set_id {}",
 		validator_set.validator_set.len(),
@@ -101,11 +167,234 @@ pub fn check_finality(
 		validator_set.set_id
 	);
 
-	is_signed_by_supermajority(num_matched_addresses, validator_set.validator_set.len())
+	is_signed_by_supermajority(num_matched_addresses, num_validators)
 		.then_some(())
 		.ok_or(eyre!("Not signed by supermajority of validator set!"))
 }
 
+/// Verifies GRANDPA commit message received via gossip.
+///
+/// Since commit messages don't contain votes ancestries,
+/// all precommits must target the commit target block.
+pub fn check_commit_message(
+	validator_set: &ValidatorSet,
+	commit_message: &CommitMessage,
+) -> Result<()> {
+	if commit_message.set_id != validator_set.set_id {
+		return Err(eyre!(
+			"Commit message set_id {} doesn't match current set_id {}",
+			commit_message.set_id,
+			validator_set.set_id
+		));
+	}
+
+	let commit = Commit::try_from(commit_message.message.clone())?;
+	if let Some(precommit) = commit.precommits.iter().find(|precommit| {
+		precommit.precommit.target_hash != commit.target_hash
+			|| precommit.precommit.target_number != commit.target_number
+	}) {
+		return Err(eyre!(
+			"Precommit target {} ({:?}) doesn't match commit target {} ({:?})",
+			precommit.precommit.target_number,
+			precommit.precommit.target_hash,
+			commit.target_number,
+			commit.target_hash
+		));
+	}
+
+	let justification = GrandpaJustification {
+		round: commit_message.round,
+		commit,
+		votes_ancestries: vec![],
+	};
+	check_finality_strict(validator_set, &justification)
+}
+
+/// Single step of a warp sync proof: a header which enacts authority set change,
+/// together with the justification which finalizes it.
+#[derive(Clone, Debug, Decode)]
+pub struct WarpSyncFragment {
+	pub header: Header,
+	pub justification: GrandpaJustification,
+}
+
+/// Warp sync proof, as returned by the GRANDPA warp sync request protocol.
+#[derive(Clone, Debug, Decode)]
+pub struct WarpSyncProof {
+	pub proofs: Vec<WarpSyncFragment>,
+	/// Set if the proof reaches the latest finalized block.
+	pub is_finished: bool,
+}
+
+/// Verifies chain of warp sync fragments, starting from the given (trusted) validator set.
+///
+/// Each fragment must be finalized by the validator set enacted by the previous one.
+/// Every fragment except the last one must contain exactly one scheduled authority set change,
+/// and fragments with forced authority set changes are rejected.
+/// Returns the validator set which is active after the last fragment.
+pub fn verify_warp_sync_fragments(
+	mut validator_set: ValidatorSet,
+	fragments: &[WarpSyncFragment],
+) -> Result<ValidatorSet> {
+	if fragments.is_empty() {
+		return Err(eyre!("Warp sync proof doesn't contain any fragments"));
+	}
+
+	for (index, fragment) in fragments.iter().enumerate() {
+		let WarpSyncFragment {
+			header,
+			justification,
+		} = fragment;
+		let header_hash: H256 = Encode::using_encoded(header, blake2_256).into();
+		if justification.commit.target_hash != header_hash
+			|| justification.commit.target_number != header.number
+		{
+			return Err(eyre!(
+				"Justification target {} ({:?}) doesn't match fragment header {} ({header_hash:?})",
+				justification.commit.target_number,
+				justification.commit.target_hash,
+				header.number
+			));
+		}
+
+		check_finality_strict(&validator_set, justification).wrap_err(format!(
+			"Warp sync fragment for block {} is not final",
+			header.number
+		))?;
+
+		// forced changes are not finalized by the previous set, so they cannot be proven
+		if has_forced_auth_set_change(header) {
+			return Err(eyre!(
+				"Warp sync fragment for block {} contains forced authority set change",
+				header.number
+			));
+		}
+
+		let mut new_auths = filter_scheduled_auth_set_changes(header);
+		if new_auths.len() > 1 {
+			return Err(eyre!(
+				"Warp sync fragment for block {} contains multiple authority set changes",
+				header.number
+			));
+		}
+		let Some(auths) = new_auths.pop() else {
+			if index == fragments.len() - 1 {
+				break;
+			}
+			return Err(eyre!(
+				"Warp sync fragment for block {} doesn't contain authority set change",
+				header.number
+			));
+		};
+
+		validator_set = ValidatorSet {
+			set_id: validator_set.set_id + 1,
+			validator_set: auths
+				.into_iter()
+				.map(|(a, _)| ed25519::Public::from_raw(a.0 .0 .0))
+				.collect(),
+			disabled: HashSet::new(),
+		};
+		info!(
+			"Validator set changed to set_id {} at block {}",
+			validator_set.set_id, header.number
+		);
+	}
+
+	Ok(validator_set)
+}
+
+/// Creates equivocation proof from two conflicting precommits, signed by the same authority in the same round.
+pub fn precommit_equivocation_proof(
+	set_id: u64,
+	round: u64,
+	first: &SignedPrecommit,
+	second: &SignedPrecommit,
+) -> Result<EquivocationProof> {
+	if first.id != second.id {
+		return Err(eyre!("Precommits are not signed by the same authority"));
+	}
+
+	let proof = EquivocationProof {
+		set_id,
+		equivocation: GrandpaEquivocation::Precommit(Equivocation {
+			round_number: round,
+			identity: first.id,
+			first: (first.precommit.clone(), first.signature.clone()),
+			second: (second.precommit.clone(), second.signature.clone()),
+		}),
+	};
+	check_equivocation(&proof)?;
+	Ok(proof)
+}
+
+/// Creates equivocation proof from two conflicting prevotes, signed by the given authority in the same round.
+pub fn prevote_equivocation_proof(
+	set_id: u64,
+	round: u64,
+	identity: Public,
+	first: (Prevote, ed25519::Signature),
+	second: (Prevote, ed25519::Signature),
+) -> Result<EquivocationProof> {
+	let proof = EquivocationProof {
+		set_id,
+		equivocation: GrandpaEquivocation::Prevote(Equivocation {
+			round_number: round,
+			identity,
+			first,
+			second,
+		}),
+	};
+	check_equivocation(&proof)?;
+	Ok(proof)
+}
+
+/// Checks that equivocation proof contains two different votes,
+/// both validly signed by the equivocating authority for the same round and set ID.
+pub fn check_equivocation(proof: &EquivocationProof) -> Result<()> {
+	match &proof.equivocation {
+		GrandpaEquivocation::Prevote(equivocation) => {
+			check_equivocation_votes(proof.set_id, equivocation, SignerMessage::PrevoteMessage)
+		},
+		GrandpaEquivocation::Precommit(equivocation) => {
+			check_equivocation_votes(proof.set_id, equivocation, SignerMessage::PrecommitMessage)
+		},
+	}
+}
+
+fn check_equivocation_votes<V: Clone + PartialEq>(
+	set_id: u64,
+	equivocation: &Equivocation<V>,
+	signer_message: impl Fn(V) -> SignerMessage,
+) -> Result<()> {
+	let (first_vote, first_signature) = &equivocation.first;
+	let (second_vote, second_signature) = &equivocation.second;
+
+	if first_vote == second_vote {
+		return Err(eyre!("Votes in equivocation proof are the same"));
+	}
+
+	for (vote, signature) in [
+		(first_vote, first_signature),
+		(second_vote, second_signature),
+	] {
+		let signed_message = Encode::encode(&(
+			&signer_message(vote.clone()),
+			&equivocation.round_number,
+			&set_id,
+		));
+		if !<ed25519::Pair as Pair>::verify(signature, signed_message, &equivocation.identity) {
+			return Err(eyre!(
+				"Invalid vote signature in equivocation proof, id: {:?}, round: {}, set_id: {set_id}",
+				equivocation.identity,
+				equivocation.round_number
+			));
+		}
+	}
+
+	Ok(())
+}
+
 fn is_signed_by_supermajority(num_signatures: usize, validator_set_size: usize) -> bool {
 	let supermajority = (validator_set_size * 2 / 3) + 1;
 	num_signatures >= supermajority
@@ -139,15 +428,26 @@ fn confirm_ancestry(
 
 #[cfg(test)]
 mod tests {
-	use codec::Encode;
+	use codec::{Decode, Encode};
 	use hex::FromHex;
+	use hex_literal::hex;
 	use sp_core::{
+		blake2_256,
 		ed25519::{self, Public, Signature},
-		Pair,
+		Pair, H256,
 	};
 	use test_case::test_case;
 
-	use crate::types::{Precommit, SignerMessage};
+	use std::collections::HashSet;
+
+	use avail_subxt::{config::substrate::DigestItem, primitives::Header};
+
+	use super::{WarpSyncFragment, WarpSyncProof};
+	use crate::types::{
+		Commit, CommitMessage, CompactCommit, EquivocationProof, GrandpaJustification, Precommit,
+		Prevote, SignedPrecommit, SignerMessage,
+	};
+	use crate::utils::test_header;
 	#[test_case(1, 1 => true)]
 	#[test_case(1, 2 => false)]
 	#[test_case(2, 2 => true)]
@@ -188,4 +488,504 @@ mod tests {
 
 		<ed25519::Pair as Pair>::verify(&sig, signed_message, &id)
 	}
+
+	fn signed_precommit(
+		pair: &ed25519::Pair,
+		target_number: u32,
+		round: u64,
+		set_id: u64,
+	) -> SignedPrecommit {
+		let precommit = Precommit {
+			target_hash: [target_number as u8; 32].into(),
+			target_number,
+		};
+		let signed_message = Encode::encode(&(
+			&SignerMessage::PrecommitMessage(precommit.clone()),
+			&round,
+			&set_id,
+		));
+		SignedPrecommit {
+			precommit,
+			signature: pair.sign(&signed_message),
+			id: pair.public(),
+		}
+	}
+
+	#[test]
+	fn precommit_equivocation_proof() {
+		let pair = ed25519::Pair::from_seed(&[1u8; 32]);
+		let first = signed_precommit(&pair, 10, 2, 1);
+		let second = signed_precommit(&pair, 11, 2, 1);
+
+		let proof = super::precommit_equivocation_proof(1, 2, &first, &second).unwrap();
+		let decoded = EquivocationProof::decode(&mut &proof.encode()[..]).unwrap();
+		assert!(super::check_equivocation(&decoded).is_ok());
+
+		// Same vote twice is not an equivocation
+		assert!(super::precommit_equivocation_proof(1, 2, &first, &first).is_err());
+		// Signatures are bound to the round and set ID
+		assert!(super::precommit_equivocation_proof(2, 2, &first, &second).is_err());
+		assert!(super::precommit_equivocation_proof(1, 3, &first, &second).is_err());
+
+		let other_pair = ed25519::Pair::from_seed(&[2u8; 32]);
+		let other = signed_precommit(&other_pair, 11, 2, 1);
+		assert!(super::precommit_equivocation_proof(1, 2, &first, &other).is_err());
+	}
+
+	fn signed_prevote(
+		pair: &ed25519::Pair,
+		target_number: u32,
+		round: u64,
+		set_id: u64,
+	) -> (Prevote, Signature) {
+		let prevote = Prevote {
+			target_hash: [target_number as u8; 32].into(),
+			target_number,
+		};
+		let signed_message = Encode::encode(&(
+			&SignerMessage::PrevoteMessage(prevote.clone()),
+			&round,
+			&set_id,
+		));
+		(prevote, pair.sign(&signed_message))
+	}
+
+	#[test]
+	fn prevote_equivocation_proof() {
+		let pair = ed25519::Pair::from_seed(&[1u8; 32]);
+		let id = pair.public();
+		let first = signed_prevote(&pair, 10, 2, 1);
+		let second = signed_prevote(&pair, 11, 2, 1);
+
+		let proof =
+			super::prevote_equivocation_proof(1, 2, id, first.clone(), second.clone()).unwrap();
+		let decoded = EquivocationProof::decode(&mut &proof.encode()[..]).unwrap();
+		assert!(super::check_equivocation(&decoded).is_ok());
+
+		// Same vote twice is not an equivocation
+		let result = super::prevote_equivocation_proof(1, 2, id, first.clone(), first.clone());
+		assert!(result.is_err());
+		// Signatures are bound to the round and set ID
+		let result = super::prevote_equivocation_proof(1, 3, id, first.clone(), second.clone());
+		assert!(result.is_err());
+		let result = super::prevote_equivocation_proof(2, 2, id, first.clone(), second.clone());
+		assert!(result.is_err());
+
+		// Votes signed by another authority
+		let other_pair = ed25519::Pair::from_seed(&[2u8; 32]);
+		let other = signed_prevote(&other_pair, 11, 2, 1);
+		let result = super::prevote_equivocation_proof(1, 2, id, first.clone(), other);
+		assert!(result.is_err());
+		let result =
+			super::prevote_equivocation_proof(1, 2, other_pair.public(), first.clone(), second);
+		assert!(result.is_err());
+
+		// Precommit signature is not valid for prevote
+		let precommit = signed_precommit(&pair, 11, 2, 1);
+		let second = (
+			Prevote {
+				target_hash: precommit.precommit.target_hash,
+				target_number: precommit.precommit.target_number,
+			},
+			precommit.signature,
+		);
+		assert!(super::prevote_equivocation_proof(1, 2, id, first, second).is_err());
+	}
+
+	#[test]
+	fn encode_equivocation_proof() {
+		let pair = ed25519::Pair::from_seed(&[1u8; 32]);
+		let first = signed_prevote(&pair, 10, 2, 1);
+		let second = signed_prevote(&pair, 11, 2, 1);
+		let proof = super::prevote_equivocation_proof(1, 2, pair.public(), first, second).unwrap();
+
+		let expected = hex!(
+			// set_id
+			"0100000000000000"
+			// GrandpaEquivocation::Prevote, round and identity
+			"00"
+			"0200000000000000"
+			"8a88e3dd7409f195fd52db2d3cba5d72ca6709bf1d94121bf3748801b40f6f5c"
+			// first prevote and signature
+			"0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a"
+			"0a000000"
+			"ff357195cb28e699dc9266a55a938adab6719a81c23730d18141c43d6e6ebca8"
+			"c4c4ea975cefe4668ea33e16bee9b46509d4f39754ef3657acf2198828f92309"
+			// second prevote and signature
+			"0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b"
+			"0b000000"
+			"717813faf353e3ce4b2d6d8e172e759efd8517cac923144b818cadf54e5c5c5d"
+			"108ea99a73610a2c9d73f441fbf66ffbe5594894f5b791d5c2cf222d82dedb00"
+		);
+		assert_eq!(proof.encode(), expected);
+	}
+
+	fn commit_message(pairs: &[ed25519::Pair], round: u64, set_id: u64) -> CommitMessage {
+		let signed_precommits = pairs
+			.iter()
+			.map(|pair| signed_precommit(pair, 10, round, set_id))
+			.collect::<Vec<_>>();
+
+		CommitMessage {
+			round,
+			set_id,
+			message: CompactCommit {
+				target_hash: [10u8; 32].into(),
+				target_number: 10,
+				precommits: signed_precommits
+					.iter()
+					.map(|p| p.precommit.clone())
+					.collect(),
+				auth_data: signed_precommits
+					.iter()
+					.map(|p| (p.signature.clone(), p.id))
+					.collect(),
+			},
+		}
+	}
+
+	#[test]
+	fn check_commit_message() {
+		let pairs = (1..=4u8)
+			.map(|seed| ed25519::Pair::from_seed(&[seed; 32]))
+			.collect::<Vec<_>>();
+		let validator_set = super::ValidatorSet {
+			set_id: 1,
+			validator_set: pairs.iter().map(|pair| pair.public()).collect(),
+			disabled: HashSet::new(),
+		};
+
+		let message = commit_message(&pairs[..3], 2, 1);
+		assert!(super::check_commit_message(&validator_set, &message).is_ok());
+
+		// Not signed by supermajority
+		let message = commit_message(&pairs[..2], 2, 1);
+		assert!(super::check_commit_message(&validator_set, &message).is_err());
+
+		// Commit message for another set
+		let message = commit_message(&pairs[..3], 2, 2);
+		assert!(super::check_commit_message(&validator_set, &message).is_err());
+
+		// Missing signature
+		let mut message = commit_message(&pairs[..3], 2, 1);
+		message.message.auth_data.pop();
+		assert!(super::check_commit_message(&validator_set, &message).is_err());
+
+		// Precommit of the same validator repeated to reach supermajority
+		let mut message = commit_message(&pairs[..2], 2, 1);
+		let precommit = message.message.precommits[0].clone();
+		let auth_data = message.message.auth_data[0].clone();
+		message.message.precommits.push(precommit);
+		message.message.auth_data.push(auth_data);
+		assert!(super::check_commit_message(&validator_set, &message).is_err());
+
+		// Precommit signed with another set_id is not accepted
+		let mut message = commit_message(&pairs[..3], 2, 1);
+		let other_set = commit_message(&pairs[..3], 2, 2);
+		message.message.auth_data[0] = other_set.message.auth_data[0].clone();
+		assert!(super::check_commit_message(&validator_set, &message).is_err());
+
+		// Precommit targeting a different block number
+		let mut message = commit_message(&pairs[..3], 2, 1);
+		message.message.target_number = 9;
+		assert!(super::check_commit_message(&validator_set, &message).is_err());
+	}
+
+	#[test]
+	fn decode_commit_message() {
+		let encoded = hex!(
+			// GossipMessage::Commit
+			"01"
+			// round and set_id
+			"0200000000000000"
+			"0100000000000000"
+			// target hash and number
+			"0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a"
+			"0a000000"
+			// single precommit
+			"04"
+			"0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a0a"
+			"0a000000"
+			// single signature and signer
+			"04"
+			"5bf9d33c700ac31a74c4165ed8b5df99d9c356c4fd6895b9cd887250f7866f9e"
+			"e95202536404e35467e95b87fcb0ed2534e8a4fea668a0a894d4b083bfc8e207"
+			"8a88e3dd7409f195fd52db2d3cba5d72ca6709bf1d94121bf3748801b40f6f5c"
+		);
+
+		let pairs = pairs(1..=1);
+		let expected = commit_message(&pairs, 2, 1);
+
+		let message = CommitMessage::decode_gossip(&encoded).unwrap();
+		assert_eq!(message.round, 2);
+		assert_eq!(message.set_id, 1);
+		assert_eq!(message.message.target_hash, expected.message.target_hash);
+		assert_eq!(message.message.target_number, 10);
+		assert_eq!(message.message.precommits, expected.message.precommits);
+		assert_eq!(message.message.auth_data, expected.message.auth_data);
+		assert!(super::check_commit_message(&validator_set(&pairs, 1), &message).is_ok());
+
+		// Payload without gossip message index, or other gossip message
+		assert!(CommitMessage::decode_gossip(&encoded[1..]).is_err());
+		assert!(CommitMessage::decode_gossip(&[&[0u8][..], &encoded[1..]].concat()).is_err());
+	}
+
+	fn scheduled_change(pairs: &[ed25519::Pair]) -> DigestItem {
+		let next_authorities = pairs
+			.iter()
+			.map(|pair| (pair.public().0, 1u64))
+			.collect::<Vec<_>>();
+		// ConsensusLog::ScheduledChange with zero delay
+		DigestItem::Consensus(*b"FRNK", (1u8, next_authorities, 0u32).encode())
+	}
+
+	fn forced_change(pairs: &[ed25519::Pair]) -> DigestItem {
+		let next_authorities = pairs
+			.iter()
+			.map(|pair| (pair.public().0, 1u64))
+			.collect::<Vec<_>>();
+		// ConsensusLog::ForcedChange with zero median last finalized block and zero delay
+		DigestItem::Consensus(*b"FRNK", (2u8, 0u32, next_authorities, 0u32).encode())
+	}
+
+	fn warp_sync_fragment(
+		header: Header,
+		pairs: &[ed25519::Pair],
+		set_id: u64,
+	) -> WarpSyncFragment {
+		let round = 1u64;
+		let target_hash: H256 = Encode::using_encoded(&header, blake2_256).into();
+		let precommit = Precommit {
+			target_hash,
+			target_number: header.number,
+		};
+		let signed_message = Encode::encode(&(
+			&SignerMessage::PrecommitMessage(precommit.clone()),
+			&round,
+			&set_id,
+		));
+		let precommits = pairs
+			.iter()
+			.map(|pair| SignedPrecommit {
+				precommit: precommit.clone(),
+				signature: pair.sign(&signed_message),
+				id: pair.public(),
+			})
+			.collect();
+
+		WarpSyncFragment {
+			justification: GrandpaJustification {
+				round,
+				commit: Commit {
+					target_hash,
+					target_number: header.number,
+					precommits,
+				},
+				votes_ancestries: vec![],
+			},
+			header,
+		}
+	}
+
+	fn pairs(seeds: std::ops::RangeInclusive<u8>) -> Vec<ed25519::Pair> {
+		seeds
+			.map(|seed| ed25519::Pair::from_seed(&[seed; 32]))
+			.collect()
+	}
+
+	fn validator_set(pairs: &[ed25519::Pair], set_id: u64) -> super::ValidatorSet {
+		super::ValidatorSet {
+			set_id,
+			validator_set: pairs.iter().map(|pair| pair.public()).collect(),
+			disabled: HashSet::new(),
+		}
+	}
+
+	#[test]
+	fn verify_warp_sync_fragments() {
+		let (set_1, set_2, set_3) = (pairs(1..=4), pairs(5..=8), pairs(9..=12));
+		let fragments = vec![
+			warp_sync_fragment(test_header(10, vec![scheduled_change(&set_2)]), &set_1, 1),
+			warp_sync_fragment(test_header(20, vec![scheduled_change(&set_3)]), &set_2, 2),
+			warp_sync_fragment(test_header(30, vec![]), &set_3, 3),
+		];
+
+		let result =
+			super::verify_warp_sync_fragments(validator_set(&set_1, 1), &fragments).unwrap();
+		assert_eq!(result.set_id, 3);
+		assert_eq!(result.validator_set, validator_set(&set_3, 3).validator_set);
+
+		// Last fragment may also enact a set change
+		let result = super::verify_warp_sync_fragments(validator_set(&set_1, 1), &fragments[..2]);
+		assert_eq!(result.unwrap().set_id, 3);
+	}
+
+	#[test]
+	fn verify_warp_sync_fragments_target_mismatch() {
+		let (set_1, set_2) = (pairs(1..=4), pairs(5..=8));
+
+		let mut fragment =
+			warp_sync_fragment(test_header(10, vec![scheduled_change(&set_2)]), &set_1, 1);
+		fragment.justification.commit.target_number = 11;
+		let result = super::verify_warp_sync_fragments(validator_set(&set_1, 1), &[fragment]);
+		assert!(result.is_err());
+
+		let mut fragment =
+			warp_sync_fragment(test_header(10, vec![scheduled_change(&set_2)]), &set_1, 1);
+		fragment.header.number = 11;
+		let result = super::verify_warp_sync_fragments(validator_set(&set_1, 1), &[fragment]);
+		assert!(result.is_err());
+	}
+
+	#[test]
+	fn verify_warp_sync_fragments_without_set_change() {
+		let (set_1, set_2) = (pairs(1..=4), pairs(5..=8));
+		let fragments = vec![
+			warp_sync_fragment(test_header(10, vec![]), &set_1, 1),
+			warp_sync_fragment(test_header(20, vec![scheduled_change(&set_2)]), &set_1, 1),
+		];
+
+		let result = super::verify_warp_sync_fragments(validator_set(&set_1, 1), &fragments);
+		assert!(result.is_err());
+	}
+
+	#[test_case(1 ; "Old set ID")]
+	#[test_case(2 ; "New set ID")]
+	fn verify_warp_sync_fragments_signed_by_old_set(set_id: u64) {
+		let (set_1, set_2) = (pairs(1..=4), pairs(5..=8));
+		let fragments = vec![
+			warp_sync_fragment(test_header(10, vec![scheduled_change(&set_2)]), &set_1, 1),
+			warp_sync_fragment(test_header(20, vec![]), &set_1, set_id),
+		];
+
+		let result = super::verify_warp_sync_fragments(validator_set(&set_1, 1), &fragments);
+		assert!(result.is_err());
+	}
+
+	#[test]
+	fn verify_warp_sync_fragments_forced_change() {
+		let (set_1, set_2) = (pairs(1..=4), pairs(5..=8));
+		let fragments = vec![
+			warp_sync_fragment(test_header(10, vec![forced_change(&set_2)]), &set_1, 1),
+			warp_sync_fragment(test_header(20, vec![]), &set_2, 2),
+		];
+
+		let result = super::verify_warp_sync_fragments(validator_set(&set_1, 1), &fragments);
+		assert!(result.is_err());
+
+		let result = super::verify_warp_sync_fragments(validator_set(&set_1, 1), &fragments[..1]);
+		assert!(result.is_err());
+	}
+
+	#[test]
+	fn verify_warp_sync_fragments_multiple_set_changes() {
+		let (set_1, set_2, set_3) = (pairs(1..=4), pairs(5..=8), pairs(9..=12));
+		let logs = vec![scheduled_change(&set_2), scheduled_change(&set_3)];
+		let fragments = vec![warp_sync_fragment(test_header(10, logs), &set_1, 1)];
+
+		let result = super::verify_warp_sync_fragments(validator_set(&set_1, 1), &fragments);
+		assert!(result.is_err());
+	}
+
+	#[test]
+	fn decode_warp_sync_proof() {
+		let encoded = hex!(
+			// two fragments
+			"08"
+			// parent hash, number, state root, extrinsics root
+			"0000000000000000000000000000000000000000000000000000000000000000"
+			"28"
+			"0000000000000000000000000000000000000000000000000000000000000000"
+			"0000000000000000000000000000000000000000000000000000000000000000"
+			// scheduled change to the next set, with zero delay
+			"040446524e4bb801048139770ea87d175f56a35466c34c7ecccb8d8a91b4ee37"
+			"a25df60f5b8fc9b394010000000000000000000000"
+			// V3 extension with empty commitment and app lookup
+			"0200000000000000000000000000000000000000000000000000000000000000"
+			"000000000000"
+			// justification: round, target hash and number
+			"0100000000000000"
+			"6d46b666a9b13d3809af8ae7424ef9d0bcf5172c2f57a0ea9caf36c5f66b5576"
+			"0a000000"
+			// single precommit, signed by the current set (set_id 1)
+			"04"
+			"6d46b666a9b13d3809af8ae7424ef9d0bcf5172c2f57a0ea9caf36c5f66b5576"
+			"0a000000"
+			"fdcfe28d9fef96042881ed0df3a1e29b3b3c3d8de270da5af288c5f4bf36858a"
+			"45494e3b5ba0aaa61a1b62b03b6156627e6a1f30172562f10434603ebb971004"
+			"8a88e3dd7409f195fd52db2d3cba5d72ca6709bf1d94121bf3748801b40f6f5c"
+			// no votes ancestries
+			"00"
+			// parent hash, number, state root, extrinsics root
+			"0000000000000000000000000000000000000000000000000000000000000000"
+			"50"
+			"0000000000000000000000000000000000000000000000000000000000000000"
+			"0000000000000000000000000000000000000000000000000000000000000000"
+			// no digest logs
+			"00"
+			// V3 extension with empty commitment and app lookup
+			"0200000000000000000000000000000000000000000000000000000000000000"
+			"000000000000"
+			// justification: round, target hash and number
+			"0100000000000000"
+			"d86e2a25c77f903cc15350bc5fd4d3a6a2377c8096eec7bb62fb5a3705acdfec"
+			"14000000"
+			// single precommit, signed by the next set (set_id 2)
+			"04"
+			"d86e2a25c77f903cc15350bc5fd4d3a6a2377c8096eec7bb62fb5a3705acdfec"
+			"14000000"
+			"f1ab9199da996de26e5730af0f1527f4a7e77d9d230fee7bb0a300455d045708"
+			"e35c088b58e04e195beec02597ce6db073ad480fd6a5b2ddbc4d2398bd366407"
+			"8139770ea87d175f56a35466c34c7ecccb8d8a91b4ee37a25df60f5b8fc9b394"
+			// no votes ancestries
+			"00"
+			// proof is finished
+			"01"
+		);
+
+		let proof = WarpSyncProof::decode(&mut &encoded[..]).unwrap();
+		assert!(proof.is_finished);
+		assert_eq!(proof.proofs.len(), 2);
+		assert_eq!(proof.proofs[0].header.number, 10);
+		assert_eq!(proof.proofs[1].header.number, 20);
+
+		let (set_1, set_2) = (pairs(1..=1), pairs(2..=2));
+		let result =
+			super::verify_warp_sync_fragments(validator_set(&set_1, 1), &proof.proofs).unwrap();
+		assert_eq!(result.set_id, 2);
+		assert_eq!(result.validator_set, validator_set(&set_2, 2).validator_set);
+	}
+
+	#[test]
+	fn verify_warp_sync_fragments_empty() {
+		let result = super::verify_warp_sync_fragments(validator_set(&pairs(1..=4), 1), &[]);
+		assert!(result.is_err());
+	}
+
+	#[test]
+	fn validator_set_is_enabled() {
+		let pairs = pairs(1..=4);
+		let mut validator_set = validator_set(&pairs, 1);
+		validator_set.disabled.insert(1);
+
+		assert!(validator_set.is_enabled(&pairs[0].public()));
+		assert!(!validator_set.is_enabled(&pairs[1].public()));
+		assert!(validator_set.is_enabled(&pairs[2].public()));
+		let unknown = ed25519::Pair::from_seed(&[5u8; 32]);
+		assert!(!validator_set.is_enabled(&unknown.public()));
+	}
+
+	#[test]
+	fn check_finality_with_disabled_validators() {
+		let pairs = pairs(1..=4);
+		let mut validator_set = validator_set(&pairs, 1);
+		validator_set.disabled.insert(0);
+
+		// Votes of disabled validators are counted towards supermajority
+		let fragment = warp_sync_fragment(test_header(10, vec![]), &pairs[..3], 1);
+		assert!(super::check_finality(&validator_set, &fragment.justification).is_ok());
+
+		let fragment = warp_sync_fragment(test_header(10, vec![]), &pairs[..2], 1);
+		assert!(super::check_finality(&validator_set, &fragment.justification).is_err());
+	}
 }
@@ -1,7 +1,7 @@
 use allow_block_list::BlockedPeers;
 use color_eyre::{eyre::WrapErr, Report, Result};
 use libp2p::{
-	autonat, dcutr, identify, identity,
+	autonat, connection_limits, dcutr, identify, identity,
 	kad::{self, PeerRecord, QueryId},
 	mdns, noise, ping, relay,
 	swarm::NetworkBehaviour,
@@ -102,6 +102,7 @@ pub struct Behaviour {
 	dcutr: dcutr::Behaviour,
 	upnp: upnp::tokio::Behaviour,
 	blocked_peers: allow_block_list::Behaviour<BlockedPeers>,
+	connection_limits: connection_limits::Behaviour,
 }
 
 fn generate_config(config: libp2p::swarm::Config, cfg: &LibP2PConfig) -> libp2p::swarm::Config {
@@ -151,6 +152,7 @@ async fn build_swarm(
 			mdns: mdns::Behaviour::new(mdns::Config::default(), key.public().to_peer_id())?,
 			upnp: upnp::tokio::Behaviour::default(),
 			blocked_peers: allow_block_list::Behaviour::default(),
+			connection_limits: connection_limits::Behaviour::new(cfg.into()),
 		})
 	};
 
@@ -20,7 +20,7 @@ use tracing::{debug, info};
 use crate::{
 	data::Database,
 	network::rpc,
-	types::{GrandpaJustification, RetryConfig, State},
+	types::{FinalityStallConfig, GrandpaJustification, RetryConfig, State},
 };
 
 mod client;
@@ -30,7 +30,7 @@ use subscriptions::SubscriptionLoop;
 const CELL_SIZE: usize = 32;
 const PROOF_SIZE: usize = 48;
 pub const CELL_WITH_PROOF_SIZE: usize = CELL_SIZE + PROOF_SIZE;
-pub use subscriptions::Event;
+pub use subscriptions::{Event, FinalityStall};
 
 pub use client::Client;
 
@@ -207,13 +207,20 @@ pub async fn init<T: Database>(
 	nodes: &[String],
 	genesis_hash: &str,
 	retry_config: RetryConfig,
+	finality_stall_config: FinalityStallConfig,
 ) -> Result<(Client, broadcast::Sender<Event>, SubscriptionLoop<T>)> {
 	let rpc_client =
 		Client::new(state.clone(), Nodes::new(nodes), genesis_hash, retry_config).await?;
 	// create output channel for RPC Subscription Events
 	let (event_sender, _) = broadcast::channel(1000);
-	let subscriptions =
-		SubscriptionLoop::new(state, db, rpc_client.clone(), event_sender.clone()).await?;
+	let subscriptions = SubscriptionLoop::new(
+		state,
+		db,
+		rpc_client.clone(),
+		event_sender.clone(),
+		finality_stall_config,
+	)
+	.await?;
 
 	Ok((rpc_client, event_sender, subscriptions))
 }
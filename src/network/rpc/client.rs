@@ -6,6 +6,7 @@ use avail_subxt::{
 	utils::H256,
 	AvailConfig,
 };
+use codec::{Decode, Encode};
 use color_eyre::{eyre::eyre, Report, Result};
 use futures::{Stream, TryFutureExt, TryStreamExt};
 use kate_recovery::{data::Cell, matrix::Position};
@@ -29,7 +30,10 @@ use tracing::{info, warn};
 use super::{Node, Nodes, Subscription, WrappedProof, CELL_WITH_PROOF_SIZE};
 use crate::{
 	consts::ExpectedNodeVariant,
-	types::{RetryConfig, RuntimeVersion, State, DEV_FLAG_GENHASH},
+	types::{
+		FeeDetails, RetryConfig, RuntimeDispatchInfo, RuntimeVersion, State, TransactionSource,
+		TransactionValidity, DEV_FLAG_GENHASH,
+	},
 };
 
 #[derive(Clone)]
@@ -284,7 +288,11 @@ impl Client {
 		Ok(header)
 	}
 
-	pub async fn get_validator_set_by_hash(&self, block_hash: H256) -> Result<Vec<Public>> {
+	/// Fetches GRANDPA authorities, along with their weights, using `GrandpaApi_grandpa_authorities` runtime API.
+	pub async fn get_grandpa_authorities_by_hash(
+		&self,
+		block_hash: H256,
+	) -> Result<Vec<(Public, u64)>> {
 		let res = self
 			.with_retries(|client| async move {
 				client
@@ -293,6 +301,29 @@ impl Client {
 					.call_raw::<Vec<(Public, u64)>>("GrandpaApi_grandpa_authorities", None)
 					.await
 			})
+			.await?;
+
+		Ok(res)
+	}
+
+	/// Fetches current GRANDPA authority set ID using `GrandpaApi_current_set_id` runtime API.
+	pub async fn get_grandpa_set_id_by_hash(&self, block_hash: H256) -> Result<u64> {
+		let res = self
+			.with_retries(|client| async move {
+				client
+					.runtime_api()
+					.at(block_hash)
+					.call_raw::<u64>("GrandpaApi_current_set_id", None)
+					.await
+			})
+			.await?;
+
+		Ok(res)
+	}
+
+	pub async fn get_validator_set_by_hash(&self, block_hash: H256) -> Result<Vec<Public>> {
+		let res = self
+			.get_grandpa_authorities_by_hash(block_hash)
 			.await?
 			.iter()
 			.map(|e| e.0)
@@ -383,6 +414,126 @@ impl Client {
 		Ok(res)
 	}
 
+	/// Validates encoded extrinsic against the state of the given block,
+	/// using `TaggedTransactionQueue_validate_transaction` runtime API of the connected node.
+	pub async fn validate_transaction(
+		&self,
+		block_hash: H256,
+		source: TransactionSource,
+		tx_bytes: &[u8],
+	) -> Result<TransactionValidity> {
+		// extrinsic is already SCALE encoded, so it is appended as is
+		let mut params = source.encode();
+		params.extend_from_slice(tx_bytes);
+		params.extend(block_hash.encode());
+
+		let res = self
+			.with_retries(|client| {
+				let params = params.clone();
+				async move {
+					client
+						.runtime_api()
+						.at(block_hash)
+						.call_raw::<TransactionValidity>(
+							"TaggedTransactionQueue_validate_transaction",
+							Some(&params),
+						)
+						.await
+				}
+			})
+			.await?;
+
+		Ok(res)
+	}
+
+	/// Refreshes runtime version and metadata used by the client, after runtime upgrade at the given block.
+	/// Returns new `spec_version`.
+	pub async fn update_runtime_version(&self, block_hash: H256) -> Result<u32> {
+		let (runtime_version, metadata) = self
+			.with_retries(|client| async move {
+				let runtime_version = client.rpc().runtime_version(Some(block_hash)).await?;
+				let metadata = client.rpc().metadata(Some(block_hash)).await?;
+				Ok((runtime_version, metadata))
+			})
+			.await?;
+
+		let spec_version = runtime_version.spec_version;
+		let client = self.current_client().await;
+		client.set_runtime_version(runtime_version);
+		client.set_metadata(metadata);
+
+		// update application wide State with the new runtime version
+		self.state.lock().unwrap().connected_node.spec_version = spec_version;
+
+		Ok(spec_version)
+	}
+
+	/// Fetches account nonce at the given block, using `AccountNonceApi_account_nonce` runtime API.
+	pub async fn get_account_nonce(&self, block_hash: H256, account: &AccountId32) -> Result<u32> {
+		let params = account.encode();
+
+		let res = self
+			.with_retries(|client| {
+				let params = params.clone();
+				async move {
+					client
+						.runtime_api()
+						.at(block_hash)
+						.call_raw::<u32>("AccountNonceApi_account_nonce", Some(&params))
+						.await
+				}
+			})
+			.await?;
+
+		Ok(res)
+	}
+
+	/// Estimates dispatch info of the encoded extrinsic, using `TransactionPaymentApi_query_info` runtime API.
+	pub async fn query_fee_info(
+		&self,
+		block_hash: H256,
+		tx_bytes: &[u8],
+	) -> Result<RuntimeDispatchInfo> {
+		self.call_transaction_payment_api(block_hash, "TransactionPaymentApi_query_info", tx_bytes)
+			.await
+	}
+
+	/// Estimates fee breakdown of the encoded extrinsic, using `TransactionPaymentApi_query_fee_details` runtime API.
+	pub async fn query_fee_details(&self, block_hash: H256, tx_bytes: &[u8]) -> Result<FeeDetails> {
+		self.call_transaction_payment_api(
+			block_hash,
+			"TransactionPaymentApi_query_fee_details",
+			tx_bytes,
+		)
+		.await
+	}
+
+	async fn call_transaction_payment_api<T: Decode>(
+		&self,
+		block_hash: H256,
+		function: &str,
+		tx_bytes: &[u8],
+	) -> Result<T> {
+		// extrinsic is already SCALE encoded, so it is appended as is, followed by its length
+		let mut params = tx_bytes.to_vec();
+		params.extend((tx_bytes.len() as u32).encode());
+
+		let res = self
+			.with_retries(|client| {
+				let params = params.clone();
+				async move {
+					client
+						.runtime_api()
+						.at(block_hash)
+						.call_raw::<T>(function, Some(&params))
+						.await
+				}
+			})
+			.await?;
+
+		Ok(res)
+	}
+
 	pub async fn get_validator_set_by_block_number(&self, block_num: u32) -> Result<Vec<Public>> {
 		let hash = self.get_block_hash(block_num).await?;
 		self.get_validator_set_by_hash(hash).await
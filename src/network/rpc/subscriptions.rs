@@ -6,20 +6,21 @@ use sp_core::{
 	ed25519::{self, Public},
 };
 use std::{
+	collections::HashSet,
 	sync::{Arc, Mutex},
-	time::Instant,
+	time::{Duration, Instant},
 };
 use tokio::sync::broadcast::Sender;
 use tokio_stream::StreamExt;
-use tracing::{debug, info, trace};
+use tracing::{debug, info, trace, warn};
 
 use super::{Client, Subscription};
 use crate::{
 	data::Database,
 	data::{FinalitySyncCheckpoint, Key},
 	finality::{check_finality, ValidatorSet},
-	types::{GrandpaJustification, OptionBlockRange, State},
-	utils::filter_auth_set_changes,
+	types::{FinalityStallConfig, GrandpaJustification, OptionBlockRange, State},
+	utils::{filter_auth_set_changes, filter_disabled_authorities, is_runtime_upgraded},
 };
 
 #[derive(Clone, Debug)]
@@ -30,6 +31,68 @@ pub enum Event {
 	},
 }
 
+/// Reason why received finalized headers are not getting verified
+#[derive(Clone, Debug, PartialEq)]
+pub enum FinalityStall {
+	/// No justifications were received for the pending headers
+	NoJustifications {
+		pending_headers: usize,
+		pending_for: Duration,
+	},
+	/// Received justifications don't match any of the pending headers
+	UnmatchedJustifications {
+		pending_headers: usize,
+		pending_justifications: usize,
+		pending_for: Duration,
+	},
+	/// None of the received justifications is signed by the current validator set
+	AuthoritySetMismatch {
+		set_id: u64,
+		pending_headers: usize,
+		pending_justifications: usize,
+		pending_for: Duration,
+	},
+}
+
+/// Interval of finality stall checks, in case no new headers or justifications are received
+const FINALITY_STALL_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Checks if number of pending headers, or time the oldest one is pending, exceeds configured limits
+fn detect_finality_stall(
+	cfg: &FinalityStallConfig,
+	set_id: u64,
+	pending_headers: usize,
+	pending_for: Duration,
+	pending_justifications: usize,
+	foreign_justifications: usize,
+) -> Option<FinalityStall> {
+	if pending_headers <= cfg.max_pending_blocks && pending_for <= cfg.max_pending_duration {
+		return None;
+	}
+
+	if pending_justifications == 0 {
+		return Some(FinalityStall::NoJustifications {
+			pending_headers,
+			pending_for,
+		});
+	}
+
+	if foreign_justifications == pending_justifications {
+		return Some(FinalityStall::AuthoritySetMismatch {
+			set_id,
+			pending_headers,
+			pending_justifications,
+			pending_for,
+		});
+	}
+
+	Some(FinalityStall::UnmatchedJustifications {
+		pending_headers,
+		pending_justifications,
+		pending_for,
+	})
+}
+
 struct BlockData {
 	justifications: Vec<GrandpaJustification>,
 	unverified_headers: Vec<(Header, Instant, ValidatorSet)>,
@@ -44,6 +107,7 @@ pub struct SubscriptionLoop<T: Database> {
 	state: Arc<Mutex<State>>,
 	db: T,
 	block_data: BlockData,
+	finality_stall_config: FinalityStallConfig,
 }
 
 impl<T: Database> SubscriptionLoop<T> {
@@ -52,6 +116,7 @@ impl<T: Database> SubscriptionLoop<T> {
 		db: T,
 		rpc_client: Client,
 		event_sender: Sender<Event>,
+		finality_stall_config: FinalityStallConfig,
 	) -> Result<Self> {
 		// get the Hash of the Finalized Head [with Retries]
 		let last_finalized_block_hash = rpc_client.get_finalized_head_hash().await?;
@@ -60,9 +125,9 @@ impl<T: Database> SubscriptionLoop<T> {
 		let validator_set = rpc_client
 			.get_validator_set_by_hash(last_finalized_block_hash)
 			.await?;
-		// fetch the set ID from storage at current height [Offline Client; no need for Retries]
+		// fetch the set ID from grandpa runtime, at the same height as the Set of Authorities [with Retries]
 		let set_id = rpc_client
-			.fetch_set_id_at(last_finalized_block_hash)
+			.get_grandpa_set_id_by_hash(last_finalized_block_hash)
 			.await?;
 		debug!("Current set: {:?}", (validator_set.clone(), set_id));
 
@@ -82,10 +147,12 @@ impl<T: Database> SubscriptionLoop<T> {
 				current_valset: ValidatorSet {
 					set_id,
 					validator_set,
+					disabled: HashSet::new(),
 				},
 				next_valset: None,
 				last_finalized_block_header: Some(last_finalized_block_header),
 			},
+			finality_stall_config,
 		})
 	}
 
@@ -94,13 +161,20 @@ impl<T: Database> SubscriptionLoop<T> {
 		let subscriptions = self.rpc_client.clone().subscription_stream().await;
 		futures::pin_mut!(subscriptions);
 
-		while let Some(result) = subscriptions.next().await {
-			match result {
-				Ok(sub) => {
-					self.handle_new_subscription(sub).await;
+		// finality stall is also checked periodically, since subscriptions can go silent
+		let mut finality_stall_check = tokio::time::interval(FINALITY_STALL_CHECK_INTERVAL);
+
+		loop {
+			tokio::select! {
+				result = subscriptions.next() => match result {
+					Some(Ok(sub)) => {
+						self.handle_new_subscription(sub).await;
+					},
+					Some(Err(err)) => return Err(eyre!(err)),
+					None => break,
 				},
-				Err(err) => return Err(eyre!(err)),
-			};
+				_ = finality_stall_check.tick() => self.check_finality_stall(),
+			}
 		}
 
 		Ok(())
@@ -113,11 +187,28 @@ impl<T: Database> SubscriptionLoop<T> {
 				self.state.lock().unwrap().latest = header.clone().number;
 				info!("Header no.: {}", header.number);
 
+				// runtime upgrade changes metadata and version used for RPC calls
+				if is_runtime_upgraded(&header) {
+					let block_hash = Encode::using_encoded(&header, blake2_256).into();
+					match self.rpc_client.update_runtime_version(block_hash).await {
+						Ok(spec_version) => {
+							info!(spec_version, "Runtime upgraded at block {}", header.number)
+						},
+						Err(error) => warn!(%error, "Failed to update runtime version"),
+					}
+				}
+
 				// if new validator set becomes active, replace the current one
 				if self.block_data.next_valset.is_some() {
 					self.block_data.current_valset = self.block_data.next_valset.take().unwrap();
 				}
 
+				// disabled validators are tracked in the current set until the next set change
+				for index in filter_disabled_authorities(&header) {
+					debug!("Validator with index {index} is disabled");
+					self.block_data.current_valset.disabled.insert(index);
+				}
+
 				// push new Unverified Header
 				self.block_data.unverified_headers.push((
 					header.clone(),
@@ -143,6 +234,7 @@ impl<T: Database> SubscriptionLoop<T> {
 					self.block_data.next_valset = Some(ValidatorSet {
 						set_id: self.block_data.current_valset.set_id + 1,
 						validator_set: new_valset,
+						disabled: HashSet::new(),
 					});
 
 					debug!("Validator set change: {:?}", self.block_data.next_valset);
@@ -158,6 +250,54 @@ impl<T: Database> SubscriptionLoop<T> {
 		}
 		// check headers
 		self.verify_and_output_block_headers().await;
+		self.check_finality_stall();
+	}
+
+	fn check_finality_stall(&self) {
+		let pending_for = self
+			.block_data
+			.unverified_headers
+			.iter()
+			.map(|(_, received_at, _)| received_at.elapsed())
+			.max()
+			.unwrap_or_default();
+
+		let current_valset = &self.block_data.current_valset;
+		let foreign_justifications = self
+			.block_data
+			.justifications
+			.iter()
+			.filter(|justification| {
+				!justification
+					.commit
+					.precommits
+					.iter()
+					.any(|precommit| current_valset.validator_set.contains(&precommit.id))
+			})
+			.count();
+
+		let finality_stall = detect_finality_stall(
+			&self.finality_stall_config,
+			current_valset.set_id,
+			self.block_data.unverified_headers.len(),
+			pending_for,
+			self.block_data.justifications.len(),
+			foreign_justifications,
+		);
+
+		// current stall is exposed through the application wide State
+		let mut state = self.state.lock().unwrap();
+		match (&state.finality_stall, &finality_stall) {
+			(None, Some(stall)) => warn!(?stall, "Finality is stalled"),
+			(Some(_), None) => info!("Finality is no longer stalled"),
+			(Some(previous), Some(stall))
+				if std::mem::discriminant(previous) != std::mem::discriminant(stall) =>
+			{
+				warn!(?stall, "Finality stall reason changed")
+			},
+			_ => (),
+		}
+		state.finality_stall = finality_stall;
 	}
 
 	async fn verify_and_output_block_headers(&mut self) {
@@ -258,3 +398,40 @@ impl<T: Database> SubscriptionLoop<T> {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{detect_finality_stall, FinalityStall};
+	use crate::types::FinalityStallConfig;
+	use std::time::Duration;
+	use test_case::test_case;
+
+	const CONFIG: FinalityStallConfig = FinalityStallConfig {
+		max_pending_blocks: 10,
+		max_pending_duration: Duration::from_secs(300),
+	};
+
+	#[test_case(0, 0, 0, 0 => None)]
+	#[test_case(10, 300, 0, 0 => None)]
+	#[test_case(2, 40, 1, 1 => None)]
+	#[test_case(11, 100, 0, 0 => Some(FinalityStall::NoJustifications { pending_headers: 11, pending_for: Duration::from_secs(100) }))]
+	#[test_case(2, 301, 0, 0 => Some(FinalityStall::NoJustifications { pending_headers: 2, pending_for: Duration::from_secs(301) }))]
+	#[test_case(11, 100, 3, 0 => Some(FinalityStall::UnmatchedJustifications { pending_headers: 11, pending_justifications: 3, pending_for: Duration::from_secs(100) }))]
+	#[test_case(11, 100, 3, 2 => Some(FinalityStall::UnmatchedJustifications { pending_headers: 11, pending_justifications: 3, pending_for: Duration::from_secs(100) }))]
+	#[test_case(11, 100, 3, 3 => Some(FinalityStall::AuthoritySetMismatch { set_id: 7, pending_headers: 11, pending_justifications: 3, pending_for: Duration::from_secs(100) }))]
+	fn finality_stall(
+		pending_headers: usize,
+		pending_seconds: u64,
+		pending_justifications: usize,
+		foreign_justifications: usize,
+	) -> Option<FinalityStall> {
+		detect_finality_stall(
+			&CONFIG,
+			7,
+			pending_headers,
+			Duration::from_secs(pending_seconds),
+			pending_justifications,
+			foreign_justifications,
+		)
+	}
+}
@@ -247,54 +247,27 @@ mod tests {
 	use std::time::Duration;
 
 	use super::*;
-	use crate::types::{self, RuntimeConfig};
-	use avail_subxt::{
-		api::runtime_types::avail_core::{
-			data_lookup::compact::CompactDataLookup,
-			header::extension::{v3::HeaderExtension, HeaderExtension::V3},
-			kate_commitment::v3::KateCommitment,
-		},
-		config::substrate::Digest,
+	use crate::{
+		types::{self, RuntimeConfig},
+		utils::test_header,
 	};
+	use avail_subxt::api::runtime_types::avail_core::header::extension::HeaderExtension::V3;
 	use hex_literal::hex;
 	use kate_recovery::{data::Cell, matrix::Position};
 	use mockall::predicate::eq;
 
 	fn default_header() -> DaHeader {
-		DaHeader {
-			parent_hash: hex!("2a75ea712b4b2c360cb7c0cdd806de4e9363ff7e37ce30788d487a258604dba3")
-				.into(),
-			number: 2,
-			state_root: hex!("6f41d5a26a34f7bc3a09d4811b444c09daaebbd5c5d67c4525f42b3ed11bef86")
-				.into(),
-			extrinsics_root: hex!(
-				"3027e34c2c75756c22770e6a3650ad68f3c9e44eed3c5ab4471742fe96678dae"
-			)
-			.into(),
-			digest: Digest { logs: vec![] },
-			extension: V3(HeaderExtension {
-				commitment: KateCommitment {
-					rows: 1,
-					cols: 4,
-					data_root: hex!(
-						"0000000000000000000000000000000000000000000000000000000000000000"
-					)
-					.into(),
-					commitment: vec![
-						181, 10, 104, 251, 33, 171, 87, 192, 13, 195, 93, 127, 215, 78, 114, 192,
-						95, 92, 167, 10, 49, 17, 20, 204, 222, 102, 70, 218, 173, 18, 30, 49, 232,
-						10, 137, 187, 186, 216, 97, 140, 16, 33, 52, 56, 170, 208, 118, 242, 181,
-						10, 104, 251, 33, 171, 87, 192, 13, 195, 93, 127, 215, 78, 114, 192, 95,
-						92, 167, 10, 49, 17, 20, 204, 222, 102, 70, 218, 173, 18, 30, 49, 232, 10,
-						137, 187, 186, 216, 97, 140, 16, 33, 52, 56, 170, 208, 118, 242,
-					],
-				},
-				app_lookup: CompactDataLookup {
-					size: 1,
-					index: vec![],
-				},
-			}),
-		}
+		let mut header = test_header(2, vec![]);
+		let V3(extension) = &mut header.extension;
+		extension.commitment.commitment = vec![
+			181, 10, 104, 251, 33, 171, 87, 192, 13, 195, 93, 127, 215, 78, 114, 192, 95, 92, 167,
+			10, 49, 17, 20, 204, 222, 102, 70, 218, 173, 18, 30, 49, 232, 10, 137, 187, 186, 216,
+			97, 140, 16, 33, 52, 56, 170, 208, 118, 242, 181, 10, 104, 251, 33, 171, 87, 192, 13,
+			195, 93, 127, 215, 78, 114, 192, 95, 92, 167, 10, 49, 17, 20, 204, 222, 102, 70, 218,
+			173, 18, 30, 49, 232, 10, 137, 187, 186, 216, 97, 140, 16, 33, 52, 56, 170, 208, 118,
+			242,
+		];
+		header
 	}
 
 	#[tokio::test]
@@ -12,6 +12,7 @@ use sp_core::{
 	twox_128, H256,
 };
 use std::{
+	collections::HashSet,
 	iter::zip,
 	sync::{Arc, Mutex},
 };
@@ -24,7 +25,7 @@ use crate::{
 	network::rpc::{self, WrappedProof},
 	shutdown::Controller,
 	types::State,
-	utils::filter_auth_set_changes,
+	utils::{filter_auth_set_changes, filter_disabled_authorities},
 };
 
 #[async_trait]
@@ -212,13 +213,35 @@ async fn get_valset_at_genesis(
 	Ok(validator_set)
 }
 
+/// Checks that trusted checkpoint is internally consistent and not ahead of the last finalized block
+fn validate_checkpoint(checkpoint: &FinalitySyncCheckpoint, last_block_num: u32) -> Result<()> {
+	if checkpoint.number == 0 {
+		return Err(eyre!("Checkpoint block number must be greater than 0"));
+	}
+	if checkpoint.number > last_block_num + 1 {
+		return Err(eyre!(
+			"Checkpoint block number {} is ahead of the last finalized block {last_block_num}",
+			checkpoint.number
+		));
+	}
+	if checkpoint.validator_set.is_empty() {
+		return Err(eyre!("Checkpoint validator set is empty"));
+	}
+	let unique = checkpoint.validator_set.iter().collect::<HashSet<_>>();
+	if unique.len() != checkpoint.validator_set.len() {
+		return Err(eyre!("Checkpoint validator set contains duplicates"));
+	}
+	Ok(())
+}
+
 pub async fn run(
 	client: impl Client,
 	shutdown: Controller<String>,
 	state: Arc<Mutex<State>>,
 	from_header: Header,
+	trusted_checkpoint: Option<FinalitySyncCheckpoint>,
 ) {
-	if let Err(error) = sync(client, state, from_header).await {
+	if let Err(error) = sync(client, state, from_header, trusted_checkpoint).await {
 		error!("Cannot sync finality {error}");
 		let _ = shutdown.trigger_shutdown(format!("Cannot sync finality {error:#}"));
 	};
@@ -228,15 +251,26 @@ pub async fn sync(
 	client: impl Client,
 	state: Arc<Mutex<State>>,
 	mut from_header: Header,
+	trusted_checkpoint: Option<FinalitySyncCheckpoint>,
 ) -> Result<()> {
 	let gen_hash = client.get_genesis_hash().await?;
 
-	let checkpoint = client.get_checkpoint()?;
+	if let Some(trusted_checkpoint) = &trusted_checkpoint {
+		validate_checkpoint(trusted_checkpoint, from_header.number)
+			.wrap_err("Invalid trusted checkpoint")?;
+	}
+
+	// stored checkpoint takes precedence, unless trusted one is further ahead
+	let checkpoint = match (client.get_checkpoint()?, trusted_checkpoint) {
+		(Some(stored), Some(trusted)) if trusted.number > stored.number => Some(trusted),
+		(stored, trusted) => stored.or(trusted),
+	};
 
 	info!("Starting finality validation sync.");
 	let mut set_id: u64;
 	let mut curr_block_num = 1u32;
 	let mut validator_set: Vec<ed25519::Public>;
+	let mut disabled = HashSet::new();
 	if let Some(ch) = checkpoint {
 		info!("Continuing from block no {}", ch.number);
 		set_id = ch.set_id;
@@ -285,6 +319,8 @@ pub async fn sync(
 		);
 		prev_hash = from_header.using_encoded(blake2_256).into();
 
+		disabled.extend(filter_disabled_authorities(&from_header));
+
 		let next_validator_set = filter_auth_set_changes(&from_header);
 		if next_validator_set.is_empty() {
 			curr_block_num += 1;
@@ -307,6 +343,7 @@ pub async fn sync(
 		let valset = ValidatorSet {
 			set_id,
 			validator_set,
+			disabled: std::mem::take(&mut disabled),
 		};
 		check_finality(&valset, &proof.0.justification.0).context("Finality sync check failed")?;
 
@@ -328,3 +365,30 @@ pub async fn sync(
 	info!("Finality is fully synced.");
 	Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use test_case::test_case;
+
+	fn checkpoint(number: u32, keys: &[u8]) -> FinalitySyncCheckpoint {
+		FinalitySyncCheckpoint {
+			number,
+			set_id: 1,
+			validator_set: keys
+				.iter()
+				.map(|&key| ed25519::Public::from_raw([key; 32]))
+				.collect(),
+		}
+	}
+
+	#[test_case(checkpoint(10, &[1, 2, 3]) => true; "Valid checkpoint")]
+	#[test_case(checkpoint(21, &[1, 2, 3]) => true; "Checkpoint after last finalized block")]
+	#[test_case(checkpoint(22, &[1, 2, 3]) => false; "Checkpoint ahead of last finalized block")]
+	#[test_case(checkpoint(0, &[1, 2, 3]) => false; "Zero block number")]
+	#[test_case(checkpoint(10, &[]) => false; "Empty validator set")]
+	#[test_case(checkpoint(10, &[1, 2, 1]) => false; "Duplicate validators")]
+	fn test_validate_checkpoint(checkpoint: FinalitySyncCheckpoint) -> bool {
+		validate_checkpoint(&checkpoint, 20).is_ok()
+	}
+}
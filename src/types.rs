@@ -1,7 +1,8 @@
 //! Shared light client structs and enums.
 
+use crate::data::FinalitySyncCheckpoint;
 use crate::network::p2p::MemoryStoreConfig;
-use crate::network::rpc::{Event, Node as RpcNode};
+use crate::network::rpc::{Event, FinalityStall, Node as RpcNode};
 use crate::utils::{extract_app_lookup, extract_kate};
 use avail_core::DataLookup;
 use avail_subxt::{primitives::Header as DaHeader, utils::H256};
@@ -93,6 +94,99 @@ pub struct RuntimeVersion {
 	transaction_version: u32,
 }
 
+/// Source of the transaction, as expected by the `TaggedTransactionQueue` runtime API
+#[derive(Clone, Copy, Debug, Encode)]
+pub enum TransactionSource {
+	InBlock,
+	Local,
+	External,
+}
+
+/// Valid transaction information, returned by the `TaggedTransactionQueue` runtime API
+#[derive(Clone, Debug, Decode)]
+pub struct ValidTransaction {
+	/// Priority of the transaction, higher priority transactions are included first
+	pub priority: u64,
+	/// Tags which need to be provided by other transactions before this one can be included
+	pub requires: Vec<Vec<u8>>,
+	/// Tags which this transaction provides
+	pub provides: Vec<Vec<u8>>,
+	/// Number of blocks for which the transaction remains valid
+	pub longevity: u64,
+	/// Whether the transaction should be propagated to other peers
+	pub propagate: bool,
+}
+
+#[derive(Clone, Debug, Decode)]
+pub enum InvalidTransaction {
+	Call,
+	Payment,
+	Future,
+	Stale,
+	BadProof,
+	AncientBirthBlock,
+	ExhaustsResources,
+	Custom(u8),
+	BadMandatory,
+	MandatoryValidation,
+	BadSigner,
+}
+
+#[derive(Clone, Debug, Decode)]
+pub enum UnknownTransaction {
+	CannotLookup,
+	NoUnsignedValidator,
+	Custom(u8),
+}
+
+#[derive(Clone, Debug, Decode)]
+pub enum TransactionValidityError {
+	Invalid(InvalidTransaction),
+	Unknown(UnknownTransaction),
+}
+
+pub type TransactionValidity = Result<ValidTransaction, TransactionValidityError>;
+
+/// Weight of the dispatch, in the format returned by the runtime
+#[derive(Clone, Copy, Debug, Decode)]
+pub struct Weight {
+	#[codec(compact)]
+	pub ref_time: u64,
+	#[codec(compact)]
+	pub proof_size: u64,
+}
+
+#[derive(Clone, Copy, Debug, Decode)]
+pub enum DispatchClass {
+	Normal,
+	Operational,
+	Mandatory,
+}
+
+/// Dispatch information, returned by the `TransactionPaymentApi_query_info` runtime API
+#[derive(Clone, Debug, Decode)]
+pub struct RuntimeDispatchInfo {
+	pub weight: Weight,
+	pub class: DispatchClass,
+	/// Inclusion fee of the transaction, without the tip
+	pub partial_fee: u128,
+}
+
+#[derive(Clone, Debug, Decode)]
+pub struct InclusionFee {
+	pub base_fee: u128,
+	pub len_fee: u128,
+	pub adjusted_weight_fee: u128,
+}
+
+/// Fee breakdown, returned by the `TransactionPaymentApi_query_fee_details` runtime API
+#[derive(Clone, Debug, Decode)]
+pub struct FeeDetails {
+	/// Inclusion fee, not set for unsigned transactions
+	pub inclusion_fee: Option<InclusionFee>,
+	pub tip: u128,
+}
+
 /// Light to app client channel message struct
 #[derive(Clone, Debug)]
 pub struct BlockVerified {
@@ -384,8 +478,15 @@ pub struct RuntimeConfig {
 	pub sync_start_block: Option<u32>,
 	/// Enable or disable synchronizing finality. If disabled, finality is assumed to be verified until the starting block at the point the LC is started and is only checked for new blocks. (default: true)
 	pub sync_finality_enable: bool,
+	/// Trusted checkpoint (block number, set ID and GRANDPA validator set active at that block) to start finality sync from, instead of genesis.
+	/// Ignored if finality sync already progressed further. (default: None).
+	pub finality_sync_checkpoint: Option<FinalitySyncCheckpoint>,
 	/// Maximum number of cells per request for proof queries (default: 30).
 	pub max_cells_per_rpc: Option<usize>,
+	/// Number of finalized headers waiting for justification, after which finality is reported as stalled (default: 10).
+	pub finality_stall_max_pending_blocks: usize,
+	/// Number of seconds a finalized header can wait for justification, after which finality is reported as stalled (default: 300).
+	pub finality_stall_max_pending_seconds: u64,
 	/// Threshold for the number of cells fetched via DHT for the app client (default: 5000)
 	pub threshold: usize,
 	/// Kademlia configuration - WARNING: Changing the default values might cause the peer to suffer poor performance!
@@ -413,6 +514,14 @@ pub struct RuntimeConfig {
 	pub task_command_buffer_size: usize,
 	pub per_connection_event_buffer_size: usize,
 	pub dial_concurrency_factor: u8,
+	/// Sets the maximum number of established incoming connections. If not set, the number is unlimited. (default: None).
+	pub max_established_incoming: Option<u32>,
+	/// Sets the maximum number of established outgoing connections. If not set, the number is unlimited. (default: None).
+	pub max_established_outgoing: Option<u32>,
+	/// Sets the maximum number of established connections to a single peer. If not set, the number is unlimited. (default: None).
+	pub max_established_per_peer: Option<u32>,
+	/// Sets the maximum number of established connections in total. If not set, the number is unlimited. (default: None).
+	pub max_established_total: Option<u32>,
 	/// Sets the timeout for a single Kademlia query. (default: 60s).
 	pub store_pruning_interval: u32,
 	/// Sets the allowed level of parallelism for iterative Kademlia queries. (default: 3).
@@ -525,6 +634,10 @@ pub struct LibP2PConfig {
 	pub task_command_buffer_size: NonZeroUsize,
 	pub per_connection_event_buffer_size: usize,
 	pub dial_concurrency_factor: NonZeroU8,
+	pub max_established_incoming: Option<u32>,
+	pub max_established_outgoing: Option<u32>,
+	pub max_established_per_peer: Option<u32>,
+	pub max_established_total: Option<u32>,
 }
 
 impl From<&LibP2PConfig> for libp2p::kad::Config {
@@ -552,6 +665,16 @@ impl From<&LibP2PConfig> for libp2p::kad::Config {
 	}
 }
 
+impl From<&LibP2PConfig> for libp2p::connection_limits::ConnectionLimits {
+	fn from(cfg: &LibP2PConfig) -> Self {
+		libp2p::connection_limits::ConnectionLimits::default()
+			.with_max_established_incoming(cfg.max_established_incoming)
+			.with_max_established_outgoing(cfg.max_established_outgoing)
+			.with_max_established_per_peer(cfg.max_established_per_peer)
+			.with_max_established(cfg.max_established_total)
+	}
+}
+
 impl From<&LibP2PConfig> for MemoryStoreConfig {
 	fn from(cfg: &LibP2PConfig) -> Self {
 		MemoryStoreConfig {
@@ -580,6 +703,10 @@ impl From<&RuntimeConfig> for LibP2PConfig {
 			per_connection_event_buffer_size: val.per_connection_event_buffer_size,
 			dial_concurrency_factor: std::num::NonZeroU8::new(val.dial_concurrency_factor)
 				.expect("Invalid dial concurrency factor"),
+			max_established_incoming: val.max_established_incoming,
+			max_established_outgoing: val.max_established_outgoing,
+			max_established_per_peer: val.max_established_per_peer,
+			max_established_total: val.max_established_total,
 		}
 	}
 }
@@ -735,6 +862,22 @@ impl From<&RuntimeConfig> for SyncClientConfig {
 	}
 }
 
+/// Finality stall detection configuration (see [RuntimeConfig] for details)
+#[derive(Clone)]
+pub struct FinalityStallConfig {
+	pub max_pending_blocks: usize,
+	pub max_pending_duration: Duration,
+}
+
+impl From<&RuntimeConfig> for FinalityStallConfig {
+	fn from(val: &RuntimeConfig) -> Self {
+		FinalityStallConfig {
+			max_pending_blocks: val.finality_stall_max_pending_blocks,
+			max_pending_duration: Duration::from_secs(val.finality_stall_max_pending_seconds),
+		}
+	}
+}
+
 /// App client configuration (see [RuntimeConfig] for details)
 pub struct AppClientConfig {
 	pub dht_parallelization_limit: usize,
@@ -782,7 +925,10 @@ impl Default for RuntimeConfig {
 			block_matrix_partition: None,
 			sync_start_block: None,
 			sync_finality_enable: false,
+			finality_sync_checkpoint: None,
 			max_cells_per_rpc: Some(30),
+			finality_stall_max_pending_blocks: 10,
+			finality_stall_max_pending_seconds: 300,
 			kad_record_ttl: 24 * 60 * 60,
 			threshold: 5000,
 			replication_factor: 5,
@@ -793,6 +939,10 @@ impl Default for RuntimeConfig {
 			task_command_buffer_size: 32,
 			per_connection_event_buffer_size: 7,
 			dial_concurrency_factor: 8,
+			max_established_incoming: None,
+			max_established_outgoing: None,
+			max_established_per_peer: None,
+			max_established_total: None,
 			store_pruning_interval: 180,
 			query_timeout: 10,
 			query_parallelism: 3,
@@ -1019,6 +1169,8 @@ pub struct State {
 	pub sync_data_verified: Option<BlockRange>,
 	pub finality_synced: bool,
 	pub connected_node: RpcNode,
+	/// Set while received finalized headers are not getting verified
+	pub finality_stall: Option<FinalityStall>,
 }
 
 pub trait OptionBlockRange {
@@ -1053,11 +1205,18 @@ impl OptionBlockRange for Option<BlockRange> {
 
 #[derive(Debug, Encode)]
 pub enum SignerMessage {
-	_DummyMessage(u32),
+	PrevoteMessage(Prevote),
 	PrecommitMessage(Precommit),
 }
 
-#[derive(Clone, Debug, Decode, Encode, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Decode, Encode, Deserialize)]
+pub struct Prevote {
+	pub target_hash: H256,
+	/// The target block's number
+	pub target_number: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Decode, Encode, Deserialize)]
 pub struct Precommit {
 	pub target_hash: H256,
 	/// The target block's number
@@ -1099,6 +1258,95 @@ impl<'de> Deserialize<'de> for GrandpaJustification {
 	}
 }
 
+/// Commit in the form gossiped by GRANDPA voters, with signatures kept apart from the precommits.
+#[derive(Clone, Debug, Decode)]
+pub struct CompactCommit {
+	pub target_hash: H256,
+	/// The target block's number.
+	pub target_number: u32,
+	pub precommits: Vec<Precommit>,
+	/// Signatures and signer Ids, in the same order as precommits.
+	pub auth_data: Vec<(ed25519::Signature, ed25519::Public)>,
+}
+
+impl TryFrom<CompactCommit> for Commit {
+	type Error = Report;
+
+	fn try_from(compact: CompactCommit) -> Result<Self, Self::Error> {
+		if compact.precommits.len() != compact.auth_data.len() {
+			return Err(eyre!(
+				"Number of precommits ({}) doesn't match number of signatures ({})",
+				compact.precommits.len(),
+				compact.auth_data.len()
+			));
+		}
+
+		let precommits = compact
+			.precommits
+			.into_iter()
+			.zip(compact.auth_data)
+			.map(|(precommit, (signature, id))| SignedPrecommit {
+				precommit,
+				signature,
+				id,
+			})
+			.collect();
+
+		Ok(Commit {
+			target_hash: compact.target_hash,
+			target_number: compact.target_number,
+			precommits,
+		})
+	}
+}
+
+/// GRANDPA commit message (`FullCommitMessage` in Substrate).
+///
+/// On the gossip network it is wrapped in the `GossipMessage::Commit` variant,
+/// so the variant index has to be stripped before decoding (see [`CommitMessage::decode_gossip`]).
+#[derive(Clone, Debug, Decode)]
+pub struct CommitMessage {
+	pub round: u64,
+	pub set_id: u64,
+	pub message: CompactCommit,
+}
+
+impl CommitMessage {
+	/// Index of the `Commit` variant of the GRANDPA `GossipMessage` enum
+	const GOSSIP_MESSAGE_INDEX: u8 = 1;
+
+	/// Decodes commit message from the encoded GRANDPA gossip message.
+	pub fn decode_gossip(message: &[u8]) -> Result<Self> {
+		let Some((&Self::GOSSIP_MESSAGE_INDEX, mut payload)) = message.split_first() else {
+			return Err(eyre!("Gossip message is not a commit message"));
+		};
+		Self::decode(&mut payload).wrap_err("Failed to decode commit message")
+	}
+}
+
+/// Two conflicting votes of the same kind, signed by the same authority in the same round.
+#[derive(Clone, Debug, Decode, Encode)]
+pub struct Equivocation<V> {
+	pub round_number: u64,
+	/// The Id of the equivocating authority.
+	pub identity: ed25519::Public,
+	pub first: (V, ed25519::Signature),
+	pub second: (V, ed25519::Signature),
+}
+
+#[derive(Clone, Debug, Decode, Encode)]
+pub enum GrandpaEquivocation {
+	Prevote(Equivocation<Prevote>),
+	Precommit(Equivocation<Precommit>),
+}
+
+/// GRANDPA equivocation proof, encoded the same way runtime expects it in `report_equivocation`.
+#[derive(Clone, Debug, Decode, Encode)]
+pub struct EquivocationProof {
+	pub set_id: u64,
+	pub equivocation: GrandpaEquivocation,
+}
+
 pub struct TimeToLive(pub Duration);
 
 impl TimeToLive {
@@ -1107,3 +1355,70 @@ impl TimeToLive {
 		Instant::now().checked_add(self.0)
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		DispatchClass, FeeDetails, InvalidTransaction, RuntimeDispatchInfo, TransactionValidity,
+		TransactionValidityError,
+	};
+	use codec::Decode;
+	use hex_literal::hex;
+
+	#[test]
+	fn decode_valid_transaction() {
+		// priority: 5, requires: [], provides: [[1, 2]], longevity: 64, propagate: true
+		let encoded = hex!("00 0500000000000000 00 04 08 0102 4000000000000000 01");
+		let validity = TransactionValidity::decode(&mut &encoded[..]).unwrap();
+
+		let valid = validity.unwrap();
+		assert_eq!(valid.priority, 5);
+		assert!(valid.requires.is_empty());
+		assert_eq!(valid.provides, vec![vec![1, 2]]);
+		assert_eq!(valid.longevity, 64);
+		assert!(valid.propagate);
+	}
+
+	#[test]
+	fn decode_invalid_transaction() {
+		let encoded = hex!("01 00 07 2a");
+		let validity = TransactionValidity::decode(&mut &encoded[..]).unwrap();
+
+		assert!(matches!(
+			validity,
+			Err(TransactionValidityError::Invalid(
+				InvalidTransaction::Custom(42)
+			))
+		));
+	}
+
+	#[test]
+	fn decode_runtime_dispatch_info() {
+		// weight: (compact 1000, compact 0), class: Operational, partial_fee: 123
+		let encoded = hex!("a10f 00 01 7b000000000000000000000000000000");
+		let info = RuntimeDispatchInfo::decode(&mut &encoded[..]).unwrap();
+
+		assert_eq!(info.weight.ref_time, 1000);
+		assert_eq!(info.weight.proof_size, 0);
+		assert!(matches!(info.class, DispatchClass::Operational));
+		assert_eq!(info.partial_fee, 123);
+	}
+
+	#[test]
+	fn decode_fee_details() {
+		let encoded = hex!("01 01000000000000000000000000000000 02000000000000000000000000000000 03000000000000000000000000000000 04000000000000000000000000000000");
+		let details = FeeDetails::decode(&mut &encoded[..]).unwrap();
+
+		let inclusion_fee = details.inclusion_fee.unwrap();
+		assert_eq!(inclusion_fee.base_fee, 1);
+		assert_eq!(inclusion_fee.len_fee, 2);
+		assert_eq!(inclusion_fee.adjusted_weight_fee, 3);
+		assert_eq!(details.tip, 4);
+
+		// Unsigned transactions don't pay inclusion fee
+		let encoded = hex!("00 00000000000000000000000000000000");
+		let details = FeeDetails::decode(&mut &encoded[..]).unwrap();
+		assert!(details.inclusion_fee.is_none());
+		assert_eq!(details.tip, 0);
+	}
+}
@@ -89,6 +89,101 @@ pub fn filter_auth_set_changes(header: &DaHeader) -> Vec<Vec<(AuthorityId, u64)>
 	new_auths
 }
 
+/// Extracts authority sets scheduled by `ScheduledChange` consensus logs, ignoring forced changes
+pub fn filter_scheduled_auth_set_changes(header: &DaHeader) -> Vec<Vec<(AuthorityId, u64)>> {
+	header
+		.digest
+		.logs
+		.iter()
+		.filter_map(|e| match &e {
+			avail_subxt::config::substrate::DigestItem::Consensus(
+				[b'F', b'R', b'N', b'K'],
+				data,
+			) => match ConsensusLog::<u32>::decode(&mut data.as_slice()) {
+				Ok(ConsensusLog::ScheduledChange(x)) => Some(x.next_authorities),
+				_ => None,
+			},
+			_ => None,
+		})
+		.collect()
+}
+
+/// Checks if header contains `ForcedChange` consensus log
+pub fn has_forced_auth_set_change(header: &DaHeader) -> bool {
+	header.digest.logs.iter().any(|e| match &e {
+		avail_subxt::config::substrate::DigestItem::Consensus([b'F', b'R', b'N', b'K'], data) => {
+			matches!(
+				ConsensusLog::<u32>::decode(&mut data.as_slice()),
+				Ok(ConsensusLog::ForcedChange(..))
+			)
+		},
+		_ => false,
+	})
+}
+
+/// Extracts indices of GRANDPA authorities disabled by `OnDisabled` consensus logs
+pub fn filter_disabled_authorities(header: &DaHeader) -> Vec<u64> {
+	header
+		.digest
+		.logs
+		.iter()
+		.filter_map(|e| match &e {
+			avail_subxt::config::substrate::DigestItem::Consensus(
+				[b'F', b'R', b'N', b'K'],
+				data,
+			) => match ConsensusLog::<u32>::decode(&mut data.as_slice()) {
+				Ok(ConsensusLog::OnDisabled(index)) => Some(index),
+				_ => None,
+			},
+			_ => None,
+		})
+		.collect()
+}
+
+/// Checks if header contains `RuntimeEnvironmentUpdated` digest, signaling runtime upgrade
+pub fn is_runtime_upgraded(header: &DaHeader) -> bool {
+	header.digest.logs.iter().any(|e| {
+		matches!(
+			e,
+			avail_subxt::config::substrate::DigestItem::RuntimeEnvironmentUpdated
+		)
+	})
+}
+
+/// Creates header with given number and digest logs, with extension of an empty 1x4 matrix
+#[cfg(test)]
+pub(crate) fn test_header(
+	number: u32,
+	logs: Vec<avail_subxt::config::substrate::DigestItem>,
+) -> DaHeader {
+	use avail_subxt::{
+		api::runtime_types::avail_core::{
+			data_lookup::compact::CompactDataLookup, kate_commitment::v3::KateCommitment,
+		},
+		config::substrate::Digest,
+	};
+
+	DaHeader {
+		parent_hash: Default::default(),
+		number,
+		state_root: Default::default(),
+		extrinsics_root: Default::default(),
+		digest: Digest { logs },
+		extension: HeaderExtension::V3(v3::HeaderExtension {
+			commitment: KateCommitment {
+				rows: 1,
+				cols: 4,
+				data_root: Default::default(),
+				commitment: vec![],
+			},
+			app_lookup: CompactDataLookup {
+				size: 1,
+				index: vec![],
+			},
+		}),
+	}
+}
+
 // TODO: Remove unused functions if not needed after next iteration
 
 #[allow(dead_code)]
@@ -113,7 +208,13 @@ fn diff_positions(positions: &[Position], cells: &[Cell]) -> Vec<Position> {
 
 #[cfg(test)]
 mod tests {
-	use super::{can_reconstruct, diff_positions};
+	use super::{
+		can_reconstruct, diff_positions, filter_disabled_authorities,
+		filter_scheduled_auth_set_changes, has_forced_auth_set_change, is_runtime_upgraded,
+		test_header,
+	};
+	use avail_subxt::config::substrate::DigestItem;
+	use codec::Encode;
 	use kate_recovery::{
 		data::Cell,
 		matrix::{Dimensions, Position},
@@ -130,6 +231,65 @@ mod tests {
 		}
 	}
 
+	// ConsensusLog::OnDisabled
+	fn on_disabled(index: u64) -> DigestItem {
+		DigestItem::Consensus(*b"FRNK", (3u8, index).encode())
+	}
+
+	#[test]
+	fn test_filter_disabled_authorities() {
+		assert!(filter_disabled_authorities(&test_header(1, vec![])).is_empty());
+
+		let disabled = test_header(1, vec![on_disabled(2), on_disabled(5)]);
+		assert_eq!(filter_disabled_authorities(&disabled), vec![2, 5]);
+
+		// Logs of other consensus engines are ignored
+		let mixed = test_header(
+			1,
+			vec![
+				DigestItem::Consensus(*b"BABE", (3u8, 1u64).encode()),
+				on_disabled(4),
+			],
+		);
+		assert_eq!(filter_disabled_authorities(&mixed), vec![4]);
+	}
+
+	#[test]
+	fn test_filter_scheduled_auth_set_changes() {
+		let next_authorities = vec![([1u8; 32], 1u64)];
+		// ConsensusLog::ScheduledChange and ConsensusLog::ForcedChange with zero delay
+		let scheduled = DigestItem::Consensus(*b"FRNK", (1u8, &next_authorities, 0u32).encode());
+		let forced = DigestItem::Consensus(*b"FRNK", (2u8, 0u32, &next_authorities, 0u32).encode());
+
+		let header = test_header(1, vec![scheduled]);
+		assert_eq!(filter_scheduled_auth_set_changes(&header).len(), 1);
+		assert!(!has_forced_auth_set_change(&header));
+
+		let header = test_header(1, vec![forced, on_disabled(1)]);
+		assert!(filter_scheduled_auth_set_changes(&header).is_empty());
+		assert!(has_forced_auth_set_change(&header));
+
+		// Logs of other consensus engines are ignored
+		let header = test_header(
+			1,
+			vec![DigestItem::Consensus(
+				*b"BABE",
+				(2u8, 0u32, &next_authorities, 0u32).encode(),
+			)],
+		);
+		assert!(!has_forced_auth_set_change(&header));
+	}
+
+	#[test]
+	fn test_is_runtime_upgraded() {
+		assert!(!is_runtime_upgraded(&test_header(1, vec![])));
+		assert!(!is_runtime_upgraded(&test_header(1, vec![on_disabled(1)])));
+		assert!(is_runtime_upgraded(&test_header(
+			1,
+			vec![on_disabled(1), DigestItem::RuntimeEnvironmentUpdated]
+		)));
+	}
+
 	#[test]
 	fn test_can_reconstruct() {
 		let dimensions = Dimensions::new(1, 4).unwrap();